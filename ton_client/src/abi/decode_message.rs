@@ -1,9 +1,11 @@
+use crate::abi::errors::DecodeError;
 use crate::abi::{Error, FunctionHeader};
 use crate::boc::internal::deserialize_object_from_boc;
 use crate::client::ClientContext;
 use crate::error::ClientResult;
 use crate::{abi::types::Abi, boc::internal::deserialize_cell_from_boc};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use ton_abi::contract::DecodedMessage;
 use ton_abi::token::Detokenizer;
@@ -45,6 +47,88 @@ pub struct ResponsibleCall<'a> {
     pub answer_id: u32,
 }
 
+/// Maps every function ID a contract ABI can produce (function input IDs,
+/// function output IDs and event IDs) to the `Function` it belongs to and the
+/// `MessageBodyType` that ID implies, so decoding a body is a single hash
+/// lookup on the leading selector instead of a brute-force trial of
+/// `decode_output`/`decode_input` against the whole ABI.
+struct FunctionIdMap {
+    ids: HashMap<u32, (Function, MessageBodyType)>,
+}
+
+impl FunctionIdMap {
+    fn build(abi: &AbiContract) -> Self {
+        let mut ids = HashMap::new();
+        for function in abi.functions().values() {
+            ids.insert(function.get_input_id(), (function.clone(), MessageBodyType::Input));
+            ids.insert(function.get_output_id(), (function.clone(), MessageBodyType::Output));
+        }
+        for event in abi.events().values() {
+            ids.insert(event.get_input_id(), (event.clone(), MessageBodyType::Event));
+        }
+        Self { ids }
+    }
+
+    fn get(&self, id: u32) -> Option<&(Function, MessageBodyType)> {
+        self.ids.get(&id)
+    }
+}
+
+/// Strips the leading function ID off `body`, along with the header in front of it when one is
+/// present, returning the decoded header (if any), the remaining body and the function ID so it
+/// can be looked up in a `FunctionIdMap`.
+///
+/// The header (signature bit plus any time/expire/pubkey fields declared in the ABI) only frames
+/// *external Input* bodies. External Output and Event bodies are just `[func_id][params]` with no
+/// header at all, so stripping one unconditionally would read the function ID from the wrong
+/// offset. Since the body's own bytes don't say which kind it is, the ID is first peeked assuming
+/// no header; only when that doesn't resolve to an Output/Event is the header actually stripped.
+fn peel_function_id(
+    abi: &AbiContract,
+    id_map: &FunctionIdMap,
+    body: SliceData,
+    is_internal: bool,
+) -> ClientResult<(Option<ton_abi::contract::FunctionHeader>, SliceData, u32)> {
+    if !is_internal {
+        let mut unheadered = body.clone();
+        if let Ok(id) = unheadered.get_next_u32() {
+            if matches!(
+                id_map.get(id),
+                Some((_, MessageBodyType::Output)) | Some((_, MessageBodyType::Event))
+            ) {
+                return Ok((None, unheadered, id));
+            }
+        }
+    }
+    let (header, mut remaining, _) =
+        ton_abi::Function::decode_header(abi.version(), body, abi.header(), is_internal)
+            .map_err(|err| Error::decode(DecodeError::HeaderDecodeFailed(err.to_string())))?;
+    let id = remaining
+        .get_next_u32()
+        .map_err(|_| Error::decode(DecodeError::BodyAbiMismatch))?;
+    Ok((Some(header), remaining, id))
+}
+
+/// Decodes `decode` (a closure wrapping a `TokenValue::decode_params` call) and, if it fails with
+/// `allow_partial` false, retries it with partial decoding allowed so a trailing-data failure can
+/// be told apart from every other decode failure and reported as the dedicated
+/// `DecodeError::TrailingDataNotAllowed` instead of a generic message.
+fn decode_token_params<E: std::fmt::Display>(
+    allow_partial: bool,
+    decode: impl Fn(bool) -> Result<Vec<ton_abi::Token>, E>,
+) -> ClientResult<Vec<ton_abi::Token>> {
+    match decode(allow_partial) {
+        Ok(tokens) => Ok(tokens),
+        Err(err) => {
+            if !allow_partial && decode(true).is_ok() {
+                Err(Error::decode(DecodeError::TrailingDataNotAllowed))
+            } else {
+                Err(Error::invalid_message_for_decode(err))
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, ApiType, PartialEq, Debug, Clone)]
 pub struct DecodedMessageBody {
     /// Type of the message body content.
@@ -78,6 +162,7 @@ impl DecodedMessageBody {
 
     pub(crate) fn decode(
         abi: &AbiContract,
+        id_map: &FunctionIdMap,
         responsible: Option<&ResponsibleCall>,
         body: SliceData,
         is_internal: bool,
@@ -120,32 +205,64 @@ impl DecodedMessageBody {
             }
             _ => {}
         }
-        if let Ok(output) = abi.decode_output(body.clone(), is_internal, allow_partial) {
-            if abi.events().get(&output.function_name).is_some() {
-                DecodedMessageBody::new(MessageBodyType::Event, output, None)
-            } else {
-                DecodedMessageBody::new(MessageBodyType::Output, output, None)
+        let (header, remaining, id) = peel_function_id(abi, id_map, body, is_internal)?;
+        match id_map.get(id) {
+            Some((function, body_type @ MessageBodyType::Output)) => {
+                let tokens = decode_token_params(allow_partial, |allow_partial| {
+                    TokenValue::decode_params(
+                        function.output_params(),
+                        remaining.clone(),
+                        &abi.version(),
+                        allow_partial,
+                    )
+                })?;
+                let decoded = DecodedMessage {
+                    function_name: function.name.clone(),
+                    tokens,
+                };
+                DecodedMessageBody::new(body_type.clone(), decoded, None)
             }
-        } else if let Ok(input) = abi.decode_input(body.clone(), is_internal, allow_partial) {
-            let (header, _, _) = ton_abi::Function::decode_header(
-                abi.version(),
-                body.clone(),
-                abi.header(),
-                is_internal,
-            )
-            .map_err(|err| {
-                Error::invalid_message_for_decode(format!("Can't decode function header: {}", err))
-            })?;
-            DecodedMessageBody::new(
-                MessageBodyType::Input,
-                input,
-                FunctionHeader::from(&header)?,
-            )
-        } else {
-            Err(Error::invalid_message_for_decode(
-                "The message body does not match the specified ABI.\n
-                Tip: Please check that you specified message's body, not full BOC.",
-            ))
+            Some((function, body_type @ MessageBodyType::Event)) => {
+                // Event parameters are declared as the event's input params, not output params.
+                let tokens = decode_token_params(allow_partial, |allow_partial| {
+                    TokenValue::decode_params(
+                        function.input_params(),
+                        remaining.clone(),
+                        &abi.version(),
+                        allow_partial,
+                    )
+                })?;
+                let decoded = DecodedMessage {
+                    function_name: function.name.clone(),
+                    tokens,
+                };
+                DecodedMessageBody::new(body_type.clone(), decoded, None)
+            }
+            Some((function, MessageBodyType::Input)) => {
+                let tokens = decode_token_params(allow_partial, |allow_partial| {
+                    TokenValue::decode_params(
+                        function.input_params(),
+                        remaining.clone(),
+                        &abi.version(),
+                        allow_partial,
+                    )
+                })?;
+                let decoded = DecodedMessage {
+                    function_name: function.name.clone(),
+                    tokens,
+                };
+                DecodedMessageBody::new(
+                    MessageBodyType::Input,
+                    decoded,
+                    FunctionHeader::from(&header.expect(
+                        "peel_function_id always strips (and returns) a header for the Input path",
+                    ))?,
+                )
+            }
+            Some((_, MessageBodyType::InternalOutput)) => {
+                Err(Error::decode(DecodeError::BodyAbiMismatch))
+            }
+            None => Err(Error::decode(DecodeError::UnknownFunctionId { id })),
         }
     }
 
@@ -156,8 +273,10 @@ impl DecodedMessageBody {
     ) -> ClientResult<DecodedMessageBody> {
         let (abi, message) = prepare_decode(&context, &params).await?;
         if let Some(body) = message.body() {
+            let id_map = FunctionIdMap::build(&abi);
             Self::decode(
                 &abi,
+                &id_map,
                 responsible,
                 body,
                 message.is_internal(),
@@ -165,9 +284,7 @@ impl DecodedMessageBody {
                 params.allow_partial,
             )
         } else {
-            Err(Error::invalid_message_for_decode(
-                "The message body is empty",
-            ))
+            Err(Error::decode(DecodeError::EmptyBody))
         }
     }
 }
@@ -198,8 +315,10 @@ pub async fn decode_message(
 ) -> ClientResult<DecodedMessageBody> {
     let (abi, message) = prepare_decode(&context, &params).await?;
     if let Some(body) = message.body() {
+        let id_map = FunctionIdMap::build(&abi);
         DecodedMessageBody::decode(
             &abi,
+            &id_map,
             None,
             body,
             message.is_internal(),
@@ -207,9 +326,7 @@ pub async fn decode_message(
             params.allow_partial,
         )
     } else {
-        Err(Error::invalid_message_for_decode(
-            "The message body is empty",
-        ))
+        Err(Error::decode(DecodeError::EmptyBody))
     }
 }
 
@@ -242,9 +359,11 @@ pub async fn decode_message_body(
 ) -> ClientResult<DecodedMessageBody> {
     let abi = params.abi.json_string()?;
     let abi = AbiContract::load(abi.as_bytes()).map_err(|x| Error::invalid_json(x))?;
+    let id_map = FunctionIdMap::build(&abi);
     let (_, body) = deserialize_cell_from_boc(&context, &params.body, "message body").await?;
     DecodedMessageBody::decode(
         &abi,
+        &id_map,
         None,
         body.into(),
         params.is_internal,
@@ -253,6 +372,84 @@ pub async fn decode_message_body(
     )
 }
 
+//------------------------------------------------------------------------------- decode_message_any
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfDecodeMessageAny {
+    /// List of contract ABIs to try decoding the message body with.
+    pub abi: Vec<Abi>,
+
+    /// Message BOC
+    pub message: String,
+
+    /// Flag allowing partial BOC decoding when ABI doesn't describe the full body BOC.
+    /// Controls decoder behaviour when after decoding all described in ABI params there are some data left in BOC:
+    /// `true` - return decoded values
+    /// `false` - return error of incomplete BOC deserialization (default)
+    #[serde(default)]
+    pub allow_partial: bool,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfDecodeMessageAny {
+    /// Decoded message body.
+    #[serde(flatten)]
+    pub decoded: DecodedMessageBody,
+
+    /// Index of the ABI (within `params.abi`) whose function-ID table matched the message body.
+    pub abi_index: u32,
+}
+
+/// Decodes message body using one of the provided ABIs without having to guess which one
+/// applies: the body's leading function ID is looked up in the given ABIs in order, the first
+/// ABI whose function-ID table holds it decodes the body, and that ABI's index is returned
+/// alongside the decoded body.
+#[api_function]
+pub async fn decode_message_any(
+    context: Arc<ClientContext>,
+    params: ParamsOfDecodeMessageAny,
+) -> ClientResult<ResultOfDecodeMessageAny> {
+    let message = deserialize_object_from_boc(&context, &params.message, "message")
+        .await
+        .map_err(|x| Error::invalid_message_for_decode(x))?
+        .object;
+    let body = message
+        .body()
+        .ok_or_else(|| Error::decode(DecodeError::EmptyBody))?;
+    let is_internal = message.is_internal();
+    let internal_dst = message.dst_ref();
+
+    let mut contracts = Vec::with_capacity(params.abi.len());
+    for abi in &params.abi {
+        let json = abi.json_string()?;
+        contracts.push(AbiContract::load(json.as_bytes()).map_err(|x| Error::invalid_json(x))?);
+    }
+
+    for (abi_index, abi) in contracts.iter().enumerate() {
+        let id_map = FunctionIdMap::build(abi);
+        let peeled = peel_function_id(abi, &id_map, body.clone(), is_internal);
+        if let Ok((_, _, id)) = peeled {
+            if id_map.get(id).is_some() {
+                let decoded = DecodedMessageBody::decode(
+                    abi,
+                    &id_map,
+                    None,
+                    body.clone(),
+                    is_internal,
+                    internal_dst,
+                    params.allow_partial,
+                )?;
+                return Ok(ResultOfDecodeMessageAny {
+                    decoded,
+                    abi_index: abi_index as u32,
+                });
+            }
+        }
+    }
+
+    Err(Error::decode(DecodeError::BodyAbiMismatch))
+}
+
 async fn prepare_decode(
     context: &ClientContext,
     params: &ParamsOfDecodeMessage,
@@ -264,3 +461,152 @@ async fn prepare_decode(
         .map_err(|x| Error::invalid_message_for_decode(x))?;
     Ok((abi, message.object))
 }
+
+//----------------------------------------------------------------------------- decode_transaction
+
+#[derive(Serialize, Deserialize, ApiType, PartialEq, Debug, Clone)]
+pub struct DecodedMessageBodyEx {
+    /// Decoded message body.
+    #[serde(flatten)]
+    pub decoded: DecodedMessageBody,
+
+    /// Index of the message within the transaction: `0` is the inbound message,
+    /// `1..` are the outbound messages in their on-chain order.
+    pub message_index: u32,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfDecodeTransaction {
+    /// Contract ABI used to decode the transaction's messages.
+    pub abi: Abi,
+
+    /// Transaction BOC
+    pub transaction: String,
+
+    /// Flag allowing partial BOC decoding when ABI doesn't describe the full body BOC.
+    /// Controls decoder behaviour when after decoding all described in ABI params there are some data left in BOC:
+    /// `true` - return decoded values
+    /// `false` - return error of incomplete BOC deserialization (default)
+    #[serde(default)]
+    pub allow_partial: bool,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfDecodeTransaction {
+    /// Decoded bodies of the transaction's inbound and outbound messages, each tagged with the
+    /// index of the message it came from. Messages whose body doesn't match the ABI are skipped.
+    pub messages: Vec<DecodedMessageBodyEx>,
+}
+
+/// Decodes all the messages of a transaction in one call, pairing responsible-function calls
+/// with the outbound message that answers them.
+///
+/// The inbound message is decoded first, as `Input`, and skipped (like every outbound message)
+/// if its body doesn't match the ABI — this relies on `peel_function_id` telling headerless
+/// external Output/Event bodies apart from the headered external Input body, so outbound
+/// external messages decode correctly. If the inbound message calls a responsible function, the
+/// `answer_id` read from its decoded header is then matched against the leading function ID of
+/// each outbound internal message addressed back to the caller: the first such message is
+/// classified as `InternalOutput` instead of being matched against the ABI on its own.
+#[api_function]
+pub async fn decode_transaction(
+    context: Arc<ClientContext>,
+    params: ParamsOfDecodeTransaction,
+) -> ClientResult<ResultOfDecodeTransaction> {
+    let abi_json = params.abi.json_string()?;
+    let abi = AbiContract::load(abi_json.as_bytes()).map_err(|x| Error::invalid_json(x))?;
+    let id_map = FunctionIdMap::build(&abi);
+
+    let transaction = deserialize_object_from_boc::<ton_block::Transaction>(
+        &context,
+        &params.transaction,
+        "transaction",
+    )
+    .await
+    .map_err(|x| Error::invalid_message_for_decode(x))?
+    .object;
+
+    let in_message = transaction
+        .in_msg_cell()
+        .map(|cell| ton_block::Message::construct_from_cell(cell))
+        .transpose()
+        .map_err(|err| {
+            Error::invalid_message_for_decode(format!(
+                "Can't deserialize transaction's inbound message: {}",
+                err
+            ))
+        })?
+        .ok_or_else(|| Error::invalid_message_for_decode("Transaction has no inbound message"))?;
+
+    let mut messages = Vec::new();
+    let mut pending_responsible: Option<(Function, MsgAddressInt, u32)> = None;
+
+    if let Some(body) = in_message.body() {
+        if let Ok(decoded) = DecodedMessageBody::decode(
+            &abi,
+            &id_map,
+            None,
+            body,
+            in_message.is_internal(),
+            in_message.dst_ref(),
+            params.allow_partial,
+        ) {
+            if let Some(function) = abi.functions().get(&decoded.name) {
+                if function.is_responsible() {
+                    if let (Some(header), Some(src)) = (&decoded.header, in_message.src_ref()) {
+                        if let Some(answer_id) = header.answer_id {
+                            pending_responsible = Some((function.clone(), src.clone(), answer_id));
+                        }
+                    }
+                }
+            }
+            messages.push(DecodedMessageBodyEx {
+                decoded,
+                message_index: 0,
+            });
+        }
+    }
+
+    let mut message_index = 1u32;
+    transaction
+        .out_msgs
+        .iterate_slices(|slice| {
+            let out_message = ton_block::Message::construct_from_cell(slice.reference(0)?)?;
+            if let Some(body) = out_message.body() {
+                let responsible = pending_responsible.as_ref().map(|(function, src, answer_id)| {
+                    ResponsibleCall {
+                        function,
+                        src,
+                        answer_id: *answer_id,
+                    }
+                });
+                if let Ok(decoded) = DecodedMessageBody::decode(
+                    &abi,
+                    &id_map,
+                    responsible.as_ref(),
+                    body,
+                    out_message.is_internal(),
+                    out_message.dst_ref(),
+                    params.allow_partial,
+                ) {
+                    if decoded.body_type == MessageBodyType::InternalOutput {
+                        pending_responsible = None;
+                    }
+                    messages.push(DecodedMessageBodyEx {
+                        decoded,
+                        message_index,
+                    });
+                }
+            }
+            message_index += 1;
+            Ok(true)
+        })
+        .map_err(|err| {
+            Error::invalid_message_for_decode(format!(
+                "Can't iterate transaction's outbound messages: {}",
+                err
+            ))
+        })?;
+
+    Ok(ResultOfDecodeTransaction { messages })
+}
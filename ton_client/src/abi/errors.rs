@@ -0,0 +1,96 @@
+use crate::error::ClientError;
+use std::fmt::Display;
+
+#[derive(ApiType, Debug, Clone, PartialEq)]
+#[repr(i32)]
+pub enum ErrorCode {
+    InvalidJson = 302,
+    InvalidMessage = 303,
+    DecodeEmptyBody = 313,
+    DecodeUnknownFunctionId = 314,
+    DecodeBodyAbiMismatch = 315,
+    DecodeHeaderFailed = 316,
+    DecodeTrailingDataNotAllowed = 317,
+}
+
+/// Structured message-decode failures, each carrying the stable numeric code from `ErrorCode`
+/// so callers can branch on the failure kind instead of string-matching the error message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The message, or the message body, has no payload to decode.
+    EmptyBody,
+
+    /// The leading function ID read from the body doesn't belong to any function or event of
+    /// the provided ABI.
+    UnknownFunctionId { id: u32 },
+
+    /// The body matches no function or event of the ABI(s), or holds no function ID after its
+    /// header.
+    BodyAbiMismatch,
+
+    /// The message header (time/expire/pubkey) could not be decoded.
+    HeaderDecodeFailed(String),
+
+    /// `allow_partial` is `false` and data was left in the body after decoding all the
+    /// parameters described in the ABI.
+    TrailingDataNotAllowed,
+}
+
+impl DecodeError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            DecodeError::EmptyBody => ErrorCode::DecodeEmptyBody,
+            DecodeError::UnknownFunctionId { .. } => ErrorCode::DecodeUnknownFunctionId,
+            DecodeError::BodyAbiMismatch => ErrorCode::DecodeBodyAbiMismatch,
+            DecodeError::HeaderDecodeFailed(_) => ErrorCode::DecodeHeaderFailed,
+            DecodeError::TrailingDataNotAllowed => ErrorCode::DecodeTrailingDataNotAllowed,
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DecodeError::EmptyBody => write!(f, "The message body is empty"),
+            DecodeError::UnknownFunctionId { id } => write!(
+                f,
+                "Unknown function ID 0x{:08x}.\nTip: check that the message body matches the \
+                specified ABI and that you passed the message's body, not the full BOC.",
+                id
+            ),
+            DecodeError::BodyAbiMismatch => {
+                write!(f, "The message body does not match the specified ABI.")
+            }
+            DecodeError::HeaderDecodeFailed(err) => {
+                write!(f, "Can't decode function header: {}", err)
+            }
+            DecodeError::TrailingDataNotAllowed => write!(
+                f,
+                "The message body has data left after decoding all the parameters described in the ABI."
+            ),
+        }
+    }
+}
+
+pub struct Error;
+
+impl Error {
+    fn error(code: ErrorCode, message: String) -> ClientError {
+        ClientError::with_code_message(code as u32, message)
+    }
+
+    pub fn invalid_json(err: impl Display) -> ClientError {
+        Self::error(ErrorCode::InvalidJson, format!("Invalid ABI json: {}", err))
+    }
+
+    pub fn invalid_message_for_decode(err: impl Display) -> ClientError {
+        Self::error(
+            ErrorCode::InvalidMessage,
+            format!("Can't decode message: {}", err),
+        )
+    }
+
+    pub fn decode(err: DecodeError) -> ClientError {
+        Self::error(err.code(), err.to_string())
+    }
+}
@@ -0,0 +1,5 @@
+mod decode_message;
+mod errors;
+
+pub use decode_message::*;
+pub use errors::{DecodeError, Error, ErrorCode};
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use zeroize::ZeroizeOnDrop;
+
+use crate::crypto::boxes::encryption_box::util::decode_fixed_hex;
+use crate::crypto::Error;
+use crate::error::ClientResult;
+use crate::ClientContext;
+
+use super::{EncryptionBox, EncryptionBoxInfo};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, ZeroizeOnDrop)]
+pub struct ChaCha20Poly1305ParamsEB {
+    /// 256-bit key. Must be encoded with `hex`.
+    pub key: String,
+    /// 96-bit nonce. Must be encoded with `hex`.
+    pub nonce: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct ChaCha20Poly1305EncryptionBox {
+    params: ChaCha20Poly1305ParamsEB,
+    hdpath: Option<String>,
+}
+
+impl ChaCha20Poly1305EncryptionBox {
+    pub fn new(params: ChaCha20Poly1305ParamsEB, hdpath: Option<String>) -> ClientResult<Self> {
+        decode_fixed_hex("key", &params.key, 32)?;
+        decode_fixed_hex("nonce", &params.nonce, 12)?;
+        Ok(Self { params, hdpath })
+    }
+
+    fn cipher(&self) -> ClientResult<ChaCha20Poly1305> {
+        let key = decode_fixed_hex("key", &self.params.key, 32)?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    fn nonce(&self) -> ClientResult<Vec<u8>> {
+        decode_fixed_hex("nonce", &self.params.nonce, 12)
+    }
+}
+
+#[async_trait::async_trait]
+impl EncryptionBox for ChaCha20Poly1305EncryptionBox {
+    async fn get_info(&self, _context: Arc<ClientContext>) -> ClientResult<EncryptionBoxInfo> {
+        Ok(EncryptionBoxInfo {
+            algorithm: Some("ChaCha20Poly1305".to_owned()),
+            hdpath: self.hdpath.clone(),
+            public: None,
+            options: Some(json!({
+                "nonce": &self.params.nonce,
+            })),
+            chunk_size: None,
+        })
+    }
+
+    async fn encrypt(&self, _context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        let cipher = self.cipher()?;
+        let nonce = self.nonce()?;
+        let encrypted = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload::from(data.as_bytes()),
+            )
+            .map_err(|err| Error::invalid_data(format!("ChaCha20Poly1305 encryption failed: {}", err)))?;
+        Ok(base64::encode(&encrypted))
+    }
+
+    async fn decrypt(&self, _context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        let cipher = self.cipher()?;
+        let nonce = self.nonce()?;
+        let encrypted = base64::decode(data)
+            .map_err(|err| Error::invalid_data(format!("Invalid base64 in encrypted data: {}", err)))?;
+        let decrypted = cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload::from(encrypted.as_slice()))
+            .map_err(|err| Error::invalid_data(format!("ChaCha20Poly1305 decryption failed: {}", err)))?;
+        String::from_utf8(decrypted)
+            .map_err(|err| Error::invalid_data(format!("Decrypted data is not valid UTF-8: {}", err)))
+    }
+}
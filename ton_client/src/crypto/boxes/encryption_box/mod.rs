@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::error::ClientResult;
+use crate::ClientContext;
+
+mod aes256_gcm_box;
+mod chacha20_poly1305_box;
+mod nacl_box;
+mod nacl_secret_box;
+mod util;
+
+pub use aes256_gcm_box::{Aes256GcmEncryptionBox, Aes256GcmParams};
+pub use chacha20_poly1305_box::{ChaCha20Poly1305EncryptionBox, ChaCha20Poly1305ParamsEB};
+pub use nacl_box::{NaclBoxParams, NaclEncryptionBox};
+pub use nacl_secret_box::{NaclSecretBoxParams, NaclSecretEncryptionBox};
+
+/// Common interface every encryption box (symmetric or asymmetric) implements, so callers can
+/// encrypt/decrypt without caring which concrete algorithm backs the box.
+#[async_trait::async_trait]
+pub trait EncryptionBox: Send + Sync {
+    async fn get_info(&self, context: Arc<ClientContext>) -> ClientResult<EncryptionBoxInfo>;
+    async fn encrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String>;
+    async fn decrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String>;
+
+    /// Encrypts one fixed-size frame of a larger payload, deriving the frame's nonce from
+    /// `chunk_index` instead of reusing a single nonce for the whole payload. The default
+    /// implementation has no notion of chunking and just encrypts `data` as a whole; box types
+    /// that advertise a `chunk_size` in `get_info` override this.
+    async fn encrypt_chunk(
+        &self,
+        context: Arc<ClientContext>,
+        data: &String,
+        _chunk_index: u32,
+    ) -> ClientResult<String> {
+        self.encrypt(context, data).await
+    }
+
+    /// Decrypts one fixed-size frame produced by `encrypt_chunk`.
+    async fn decrypt_chunk(
+        &self,
+        context: Arc<ClientContext>,
+        data: &String,
+        _chunk_index: u32,
+    ) -> ClientResult<String> {
+        self.decrypt(context, data).await
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct EncryptionBoxInfo {
+    /// Derivation path, for boxes derived from a crypto HD key.
+    pub hdpath: Option<String>,
+
+    /// Algorithm used by the encryption box.
+    pub algorithm: Option<String>,
+
+    /// Algorithm-specific options, e.g. `NaclBox`'s `their_public` and `nonce`.
+    pub options: Option<serde_json::Value>,
+
+    /// Public information, depending on algorithm.
+    pub public: Option<String>,
+
+    /// Size, in bytes, of one frame accepted by `encrypt_chunk`/`decrypt_chunk`. `None` for
+    /// boxes that don't support chunked streaming and only encrypt/decrypt a payload whole.
+    pub chunk_size: Option<u32>,
+}
+
+/// Selects the algorithm behind an `EncryptionBox`, tagged by `algorithm` so `get_info` can
+/// report back exactly which one a box was created with.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "algorithm", content = "options")]
+pub enum EncryptionAlgorithm {
+    NaclBox(NaclBoxParams),
+    NaclSecretBox(NaclSecretBoxParams),
+    ChaCha20Poly1305(ChaCha20Poly1305ParamsEB),
+    AES256GCM(Aes256GcmParams),
+}
+
+/// Builds the concrete `EncryptionBox` implementation selected by `algorithm`.
+pub(crate) fn create_encryption_box(
+    algorithm: EncryptionAlgorithm,
+    hdpath: Option<String>,
+) -> ClientResult<Box<dyn EncryptionBox>> {
+    Ok(match algorithm {
+        EncryptionAlgorithm::NaclBox(params) => Box::new(NaclEncryptionBox::new(params, hdpath)),
+        EncryptionAlgorithm::NaclSecretBox(params) => {
+            Box::new(NaclSecretEncryptionBox::new(params, hdpath)?)
+        }
+        EncryptionAlgorithm::ChaCha20Poly1305(params) => {
+            Box::new(ChaCha20Poly1305EncryptionBox::new(params, hdpath)?)
+        }
+        EncryptionAlgorithm::AES256GCM(params) => {
+            Box::new(Aes256GcmEncryptionBox::new(params, hdpath)?)
+        }
+    })
+}
@@ -2,9 +2,16 @@ use std::sync::Arc;
 
 use zeroize::ZeroizeOnDrop;
 
-use crate::ClientContext;
-use crate::crypto::{EncryptionBox, EncryptionBoxInfo, nacl_box, nacl_box_open, ParamsOfNaclBox, ParamsOfNaclBoxOpen};
+use crate::crypto::boxes::encryption_box::util::nonce_for_chunk;
+use crate::crypto::{nacl_box, nacl_box_open, ParamsOfNaclBox, ParamsOfNaclBoxOpen};
 use crate::error::ClientResult;
+use crate::ClientContext;
+
+use super::{EncryptionBox, EncryptionBoxInfo};
+
+/// Plaintext bytes encoded into a single streamed frame by `encrypt_chunk`/`decrypt_chunk`.
+/// Reported via `get_info` so the receiving side can split its input into matching frames.
+const CHUNK_SIZE: u32 = 4096;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, ZeroizeOnDrop)]
 pub struct NaclBoxParams {
@@ -26,6 +33,14 @@ impl NaclEncryptionBox {
     pub fn new(params: NaclBoxParams, hdpath: Option<String>) -> Self {
         Self { params, hdpath }
     }
+
+    /// Nonce for chunk `chunk_index` of a streaming encryption/decryption, derived by adding
+    /// `chunk_index` to the configured nonce treated as a big-endian counter.
+    fn chunk_nonce(&self, chunk_index: u32) -> ClientResult<String> {
+        let nonce = hex::decode(&self.params.nonce)
+            .map_err(|err| crate::crypto::Error::invalid_data(format!("Invalid nonce: {}", err)))?;
+        Ok(hex::encode(nonce_for_chunk(&nonce, chunk_index)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -38,7 +53,8 @@ impl EncryptionBox for NaclEncryptionBox {
             options: Some(json!({
                 "their_public": &self.params.their_public,
                 "nonce": hex::encode(&self.params.nonce),
-            }))
+            })),
+            chunk_size: Some(CHUNK_SIZE),
         })
     }
 
@@ -59,4 +75,42 @@ impl EncryptionBox for NaclEncryptionBox {
             secret: self.params.secret.clone(),
         }).map(|result| result.decrypted)
     }
+
+    /// Each frame carries its own authentication tag, so a failure to decrypt one frame doesn't
+    /// affect the others.
+    async fn encrypt_chunk(
+        &self,
+        context: Arc<ClientContext>,
+        data: &String,
+        chunk_index: u32,
+    ) -> ClientResult<String> {
+        nacl_box(
+            context,
+            ParamsOfNaclBox {
+                decrypted: data.clone(),
+                nonce: self.chunk_nonce(chunk_index)?,
+                their_public: self.params.their_public.clone(),
+                secret: self.params.secret.clone(),
+            },
+        )
+        .map(|result| result.encrypted)
+    }
+
+    async fn decrypt_chunk(
+        &self,
+        context: Arc<ClientContext>,
+        data: &String,
+        chunk_index: u32,
+    ) -> ClientResult<String> {
+        nacl_box_open(
+            context,
+            ParamsOfNaclBoxOpen {
+                encrypted: data.clone(),
+                nonce: self.chunk_nonce(chunk_index)?,
+                their_public: self.params.their_public.clone(),
+                secret: self.params.secret.clone(),
+            },
+        )
+        .map(|result| result.decrypted)
+    }
 }
\ No newline at end of file
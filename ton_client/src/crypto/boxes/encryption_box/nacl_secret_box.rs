@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use zeroize::ZeroizeOnDrop;
+
+use crate::crypto::boxes::encryption_box::util::decode_fixed_hex;
+use crate::crypto::{
+    nacl_secret_box, nacl_secret_box_open, ParamsOfNaclSecretBox, ParamsOfNaclSecretBoxOpen,
+};
+use crate::error::ClientResult;
+use crate::ClientContext;
+
+use super::{EncryptionBox, EncryptionBoxInfo};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, ZeroizeOnDrop)]
+pub struct NaclSecretBoxParams {
+    /// 256-bit key. Must be encoded with `hex`.
+    pub key: String,
+    /// 192-bit nonce. Must be encoded with `hex`.
+    pub nonce: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct NaclSecretEncryptionBox {
+    params: NaclSecretBoxParams,
+    hdpath: Option<String>,
+}
+
+impl NaclSecretEncryptionBox {
+    pub fn new(params: NaclSecretBoxParams, hdpath: Option<String>) -> ClientResult<Self> {
+        decode_fixed_hex("key", &params.key, 32)?;
+        decode_fixed_hex("nonce", &params.nonce, 24)?;
+        Ok(Self { params, hdpath })
+    }
+}
+
+#[async_trait::async_trait]
+impl EncryptionBox for NaclSecretEncryptionBox {
+    async fn get_info(&self, _context: Arc<ClientContext>) -> ClientResult<EncryptionBoxInfo> {
+        Ok(EncryptionBoxInfo {
+            algorithm: Some("NaclSecretBox".to_owned()),
+            hdpath: self.hdpath.clone(),
+            public: None,
+            options: Some(json!({
+                "nonce": hex::encode(&self.params.nonce),
+            })),
+            chunk_size: None,
+        })
+    }
+
+    async fn encrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        nacl_secret_box(
+            context,
+            ParamsOfNaclSecretBox {
+                decrypted: data.clone(),
+                nonce: self.params.nonce.clone(),
+                key: self.params.key.clone(),
+            },
+        )
+        .map(|result| result.encrypted)
+    }
+
+    async fn decrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        nacl_secret_box_open(
+            context,
+            ParamsOfNaclSecretBoxOpen {
+                encrypted: data.clone(),
+                nonce: self.params.nonce.clone(),
+                key: self.params.key.clone(),
+            },
+        )
+        .map(|result| result.decrypted)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::crypto::Error;
+use crate::error::ClientResult;
+
+/// Decodes a `hex`-encoded key or nonce and checks it is exactly `expected_bytes` long, so a
+/// malformed `EncryptionBox` param fails fast instead of surfacing as an opaque cipher error.
+pub(crate) fn decode_fixed_hex(
+    name: &str,
+    value: &str,
+    expected_bytes: usize,
+) -> ClientResult<Vec<u8>> {
+    let bytes =
+        hex::decode(value).map_err(|err| Error::invalid_data(format!("Invalid `{}`: {}", name, err)))?;
+    if bytes.len() != expected_bytes {
+        return Err(Error::invalid_data(format!(
+            "Invalid `{}` length: expected {} bytes, got {}",
+            name,
+            expected_bytes,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Derives the nonce for chunk `chunk_index` of a streaming encryption by treating `nonce` as a
+/// big-endian counter and adding the chunk index to it, so encrypting or decrypting the same
+/// chunk twice always uses the same nonce while different chunks never reuse one.
+pub(crate) fn nonce_for_chunk(nonce: &[u8], chunk_index: u32) -> Vec<u8> {
+    let mut result = nonce.to_vec();
+    let mut carry = chunk_index as u64;
+    for byte in result.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        let sum = *byte as u64 + (carry & 0xff);
+        *byte = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+    result
+}